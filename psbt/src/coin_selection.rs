@@ -0,0 +1,317 @@
+// Modern, minimalistic & standard-compliant Bitcoin library.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Designed in 2019-2025 by Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+// Written in 2024-2025 by Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association, Switzerland.
+// Copyright (C) 2024-2025 LNP/BP Labs, Institute for Distributed and Cognitive Systems (InDCS).
+// Copyright (C) 2019-2025 Dr Maxim Orlovsky.
+// All rights under the above copyrights are reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use derive::Sats;
+
+use crate::{FeeRate, Utxo};
+
+/// Upper bound on the number of nodes the branch-and-bound search will visit before giving up
+/// and deferring to [`SingleRandomDraw`] (and then [`LargestFirst`]). Keeps selection on large
+/// UTXO sets bounded in time.
+const BNB_TOTAL_TRIES: usize = 100_000;
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum CoinSelectionError {
+    /// the available coins ({available} sats) are insufficient to cover the target of {target}
+    /// sats plus fees.
+    InsufficientFunds { available: Sats, target: Sats },
+}
+
+/// Result of a successful [`CoinSelector::select`] call.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CoinSelection {
+    /// The UTXOs chosen to fund the transaction.
+    pub selected: Vec<Utxo>,
+    /// The absolute fee paid by the inputs selected, at the requested fee rate, for spending
+    /// just these inputs (excluding the cost of any change output).
+    pub fee: Sats,
+    /// The "waste" of this selection: the amount left over after the target and fee are
+    /// covered, which will either fund a change output or be added to the fee. Lower is better.
+    pub waste: Sats,
+}
+
+/// A strategy for choosing which wallet UTXOs fund a transaction, mirroring the role of BDK's
+/// `coin_selection` module.
+pub trait CoinSelector {
+    /// Chooses a subset of `candidates` whose total value covers `target` plus the fee of
+    /// spending the chosen inputs themselves, at `fee_rate`.
+    ///
+    /// `input_weight` is the estimated weight, in weight units, of satisfying a single input
+    /// with the wallet's descriptor. `cost_of_change` is the estimated cost - in fees - of
+    /// creating a change output now and later spending it, used to size the acceptable target
+    /// range.
+    fn select(
+        &self,
+        candidates: &[Utxo],
+        target: Sats,
+        fee_rate: FeeRate,
+        input_weight: u32,
+        cost_of_change: Sats,
+    ) -> Result<CoinSelection, CoinSelectionError>;
+}
+
+/// The effective value of a UTXO at a given fee rate: its value minus the fee needed to spend
+/// it. May be negative for small UTXOs at a high fee rate.
+fn effective_value(utxo: &Utxo, fee_rate: FeeRate, input_weight: u32) -> i64 {
+    let input_fee = fee_rate.to_fee((input_weight + 3) / 4).to_sats() as i64;
+    utxo.value.to_sats() as i64 - input_fee
+}
+
+/// Branch-and-bound coin selection, as used by Bitcoin Core and BDK.
+///
+/// Performs a depth-first search over candidates sorted by descending effective value, at each
+/// node branching on whether to include or exclude the candidate, looking for a selection whose
+/// total effective value falls in `[target, target + cost_of_change]` - i.e. one that needs no
+/// change output and wastes as little as possible. If no such exact match is found within
+/// [`BNB_TOTAL_TRIES`] attempts, falls back to [`SingleRandomDraw`] and then [`LargestFirst`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct BranchAndBound;
+
+impl BranchAndBound {
+    /// `remaining_sum[i]` is the sum of `pool[i..]`, i.e. the most this branch could still add
+    /// if every remaining candidate were included - used to prune branches that can never reach
+    /// `lower`, not just ones that have already overshot `upper`.
+    fn search(
+        pool: &[i64],
+        remaining_sum: &[i64],
+        index: usize,
+        sum: i64,
+        selected: &mut Vec<usize>,
+        lower: i64,
+        upper: i64,
+        tries: &mut usize,
+        best: &mut Option<(Vec<usize>, i64)>,
+    ) {
+        *tries += 1;
+        if sum > upper || *tries > BNB_TOTAL_TRIES {
+            return;
+        }
+        if sum >= lower {
+            let waste = sum - lower;
+            if best.as_ref().is_none_or(|(_, best_waste)| waste < *best_waste) {
+                *best = Some((selected.clone(), waste));
+            }
+            // An exact (or near-exact) match was found at this depth; do not branch further
+            // down this path since including more inputs can only add waste.
+            return;
+        }
+        if index >= pool.len() || sum + remaining_sum[index] < lower {
+            return;
+        }
+        selected.push(index);
+        Self::search(pool, remaining_sum, index + 1, sum + pool[index], selected, lower, upper, tries, best);
+        selected.pop();
+        Self::search(pool, remaining_sum, index + 1, sum, selected, lower, upper, tries, best);
+    }
+}
+
+impl CoinSelector for BranchAndBound {
+    fn select(
+        &self,
+        candidates: &[Utxo],
+        target: Sats,
+        fee_rate: FeeRate,
+        input_weight: u32,
+        cost_of_change: Sats,
+    ) -> Result<CoinSelection, CoinSelectionError> {
+        let mut indexed: Vec<(usize, i64)> = candidates
+            .iter()
+            .map(|utxo| effective_value(utxo, fee_rate, input_weight))
+            .enumerate()
+            .filter(|(_, value)| *value > 0)
+            .collect();
+        indexed.sort_by(|a, b| b.1.cmp(&a.1));
+        let pool: Vec<i64> = indexed.iter().map(|(_, value)| *value).collect();
+
+        let lower = target.to_sats() as i64;
+        let upper = lower + cost_of_change.to_sats() as i64;
+
+        let mut remaining_sum = vec![0i64; pool.len() + 1];
+        for i in (0..pool.len()).rev() {
+            remaining_sum[i] = remaining_sum[i + 1] + pool[i];
+        }
+
+        let mut best = None;
+        let mut tries = 0;
+        Self::search(&pool, &remaining_sum, 0, 0, &mut Vec::new(), lower, upper, &mut tries, &mut best);
+
+        if let Some((picks, waste)) = best {
+            let selected = picks.iter().map(|&i| candidates[indexed[i].0].clone()).collect();
+            let fee = fee_rate.to_fee((picks.len() as u32 * input_weight + 3) / 4);
+            return Ok(CoinSelection {
+                selected,
+                fee,
+                waste: Sats::from_sats(waste.max(0) as u64),
+            });
+        }
+
+        SingleRandomDraw
+            .select(candidates, target, fee_rate, input_weight, cost_of_change)
+            .or_else(|_| LargestFirst.select(candidates, target, fee_rate, input_weight, cost_of_change))
+    }
+}
+
+/// Single Random Draw: shuffles candidates into a random order and accumulates them until the
+/// target plus the fee of the inputs taken so far is covered, as used by BDK when
+/// branch-and-bound fails to find an exact match. Spreads UTXO usage evenly over time instead of
+/// always draining the largest coins first, at the cost of (usually) creating a change output.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct SingleRandomDraw;
+
+impl CoinSelector for SingleRandomDraw {
+    fn select(
+        &self,
+        candidates: &[Utxo],
+        target: Sats,
+        fee_rate: FeeRate,
+        input_weight: u32,
+        _cost_of_change: Sats,
+    ) -> Result<CoinSelection, CoinSelectionError> {
+        let order = shuffled_indices(candidates.len());
+
+        let mut selected = Vec::new();
+        let mut total = Sats::ZERO;
+        let mut fee = Sats::ZERO;
+        for i in order {
+            let utxo = &candidates[i];
+            selected.push(utxo.clone());
+            total = total.checked_add(utxo.value).unwrap_or(total);
+            fee = fee_rate.to_fee((selected.len() as u32 * input_weight + 3) / 4);
+            if let Some(remaining) = total.checked_sub(fee) {
+                if remaining >= target {
+                    let waste = remaining.checked_sub(target).unwrap_or(Sats::ZERO);
+                    return Ok(CoinSelection { selected, fee, waste });
+                }
+            }
+        }
+
+        Err(CoinSelectionError::InsufficientFunds { available: total, target })
+    }
+}
+
+/// Returns `0..len` in a random order, seeded from OS entropy via [`RandomState`] so no `rand`
+/// dependency is needed just for coin selection's Single Random Draw fallback.
+fn shuffled_indices(len: usize) -> Vec<usize> {
+    let state = RandomState::new();
+    let mut indices: Vec<usize> = (0..len).collect();
+    for i in (1..len).rev() {
+        let mut hasher = state.build_hasher();
+        hasher.write_usize(i);
+        let r = (hasher.finish() as usize) % (i + 1);
+        indices.swap(i, r);
+    }
+    indices
+}
+
+/// A simple fallback selector: sorts candidates by descending value and accumulates them until
+/// the target plus the fee of the inputs taken so far is covered.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct LargestFirst;
+
+impl CoinSelector for LargestFirst {
+    fn select(
+        &self,
+        candidates: &[Utxo],
+        target: Sats,
+        fee_rate: FeeRate,
+        input_weight: u32,
+        _cost_of_change: Sats,
+    ) -> Result<CoinSelection, CoinSelectionError> {
+        let mut sorted: Vec<&Utxo> = candidates.iter().collect();
+        sorted.sort_by(|a, b| b.value.cmp(&a.value));
+
+        let mut selected = Vec::new();
+        let mut total = Sats::ZERO;
+        let mut fee = Sats::ZERO;
+        for utxo in sorted {
+            selected.push(utxo.clone());
+            total = total.checked_add(utxo.value).unwrap_or(total);
+            fee = fee_rate.to_fee((selected.len() as u32 * input_weight + 3) / 4);
+            if let Some(remaining) = total.checked_sub(fee) {
+                if remaining >= target {
+                    let waste = remaining.checked_sub(target).unwrap_or(Sats::ZERO);
+                    return Ok(CoinSelection { selected, fee, waste });
+                }
+            }
+        }
+
+        Err(CoinSelectionError::InsufficientFunds { available: total, target })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(pool: &[i64], lower: i64, upper: i64) -> Option<(Vec<usize>, i64)> {
+        let remaining_sum = {
+            let mut remaining_sum = vec![0i64; pool.len() + 1];
+            for i in (0..pool.len()).rev() {
+                remaining_sum[i] = remaining_sum[i + 1] + pool[i];
+            }
+            remaining_sum
+        };
+        let mut best = None;
+        let mut tries = 0;
+        BranchAndBound::search(pool, &remaining_sum, 0, 0, &mut Vec::new(), lower, upper, &mut tries, &mut best);
+        best
+    }
+
+    #[test]
+    fn search_finds_an_exact_match_within_range() {
+        let (picks, waste) = search(&[500, 300, 100], 400, 450).unwrap();
+        // Only `300 + 100 = 400` falls in [400, 450]; `500` alone overshoots past `upper`.
+        assert_eq!(picks, vec![1, 2]);
+        assert_eq!(waste, 0);
+    }
+
+    #[test]
+    fn search_prefers_the_lowest_waste_match() {
+        let (picks, waste) =
+            search(&[300, 250, 100], 300, 1_000).expect("a match should be found");
+        // `300` alone is an exact match (waste 0); `250 + 100 = 350` would waste 50 more.
+        assert_eq!(picks, vec![0]);
+        assert_eq!(waste, 0);
+    }
+
+    #[test]
+    fn search_finds_nothing_when_the_target_is_unreachable() {
+        // The whole pool sums to 600, well short of the 1_000 lower bound; the remaining-sum
+        // pruning should cut every branch without finding a false match.
+        assert_eq!(search(&[300, 200, 100], 1_000, 1_100), None);
+    }
+
+    #[test]
+    fn shuffled_indices_is_a_permutation() {
+        let order = shuffled_indices(10);
+        assert_eq!(order.len(), 10);
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -22,6 +22,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
@@ -30,10 +31,154 @@ use derive::{
     Address, AddressNetwork, AddressParseError, Keychain, LockTime, Network, NormalIndex, Outpoint,
     Sats, ScriptPubkey, SeqNo, Terminal, Vout,
 };
-use descriptors::Descriptor;
+use descriptors::{Class, Descriptor};
 
+use crate::coin_selection::{CoinSelectionError, CoinSelector};
+use crate::invoice::Invoice;
 use crate::{Prevout, Psbt, PsbtError, PsbtVer, UnsignedTx};
 
+/// Weight, in weight units, of everything in a transaction other than its inputs and outputs:
+/// the version field, the segwit marker and flag, the input/output count varints and the
+/// lock time, all counted at the non-witness weight multiplier of 4 except for the marker and
+/// flag, which are witness-only bytes.
+pub(crate) const TX_BASE_WEIGHT: u32 = (4 + 4 + 2) * 4 + 2;
+
+/// Weight of a `TxOut` serialization, excluding the `script_pubkey` content itself: the 8-byte
+/// amount plus the script length varint, counted at the non-witness weight multiplier of 4.
+pub(crate) const TXOUT_BASE_WEIGHT: u32 = (8 + 1) * 4;
+
+/// Weight of a `TxIn` serialization, excluding any scriptSig content or witness: the outpoint,
+/// an empty scriptSig length byte and the sequence number, counted at the non-witness weight
+/// multiplier of 4.
+pub(crate) const TXIN_BASE_WEIGHT: u32 = (32 + 4 + 1 + 4) * 4;
+
+/// The minimum non-dust value for `spk`, matched against the standard output templates; used for
+/// `subtract_fee_from` targets, which (unlike a wallet's own change output) may pay any recipient
+/// script type regardless of the wallet's own descriptor `class`. Falls back to `class`'s own
+/// dust limit for non-standard/custom scripts (e.g. from [`Beneficiary::with_script`]).
+fn dust_limit_for(spk: &ScriptPubkey, class: Class) -> Sats {
+    let bytes: &[u8] = spk.as_ref();
+    match bytes {
+        [0x76, 0xA9, 0x14, .., 0x88, 0xAC] if bytes.len() == 25 => Sats::from_sats(546), // P2PKH
+        [0xA9, 0x14, .., 0x87] if bytes.len() == 23 => Sats::from_sats(540), // P2SH
+        [0x00, 0x14, ..] if bytes.len() == 22 => Sats::from_sats(294), // P2WPKH
+        [0x00, 0x20, ..] if bytes.len() == 34 => Sats::from_sats(330), // P2WSH
+        [0x51, 0x20, ..] if bytes.len() == 34 => Sats::from_sats(330), // P2TR
+        _ => class.dust_limit(),
+    }
+}
+
+/// Splits `fee` proportionally across `targets` - each a `(output_index, amount, dust_limit)` -
+/// returning the reduced amount for each index, for the `subtract_fee_from` path of
+/// [`PsbtConstructor::construct_psbt`]. The last target absorbs whatever the proportional shares
+/// left unassigned due to integer rounding, so the shares always sum to exactly `fee`.
+///
+/// Errors if any target's amount is zero (it could not bear a meaningful share) or if reducing a
+/// target by its share would leave it at or below its own dust limit.
+fn compute_fee_shares(
+    fee: Sats,
+    targets: &[(usize, Sats, Sats)],
+) -> Result<Vec<(usize, Sats)>, ConstructionError> {
+    for (index, amount, _) in targets {
+        if *amount == Sats::ZERO {
+            return Err(ConstructionError::SubtractFeeFromZeroValue(*index));
+        }
+    }
+    let target_total = targets
+        .iter()
+        .try_fold(Sats::ZERO, |acc, (_, amount, _)| acc.checked_add(*amount))
+        .ok_or(ConstructionError::Overflow(Sats::ZERO))?;
+
+    let mut fee_remaining = fee;
+    let last = targets.len() - 1;
+    let mut result = Vec::with_capacity(targets.len());
+    for (i, (index, amount, dust_limit)) in targets.iter().enumerate() {
+        let share = if i == last {
+            fee_remaining
+        } else {
+            // Widen to u128 for the multiply: `fee * amount` can exceed u64 well before either
+            // factor alone would, since sats fit the full range of each.
+            let share =
+                (fee.to_sats() as u128 * amount.to_sats() as u128) / target_total.to_sats() as u128;
+            let share = Sats::from_sats(share as u64);
+            fee_remaining = fee_remaining.checked_sub(share).unwrap_or(Sats::ZERO);
+            share
+        };
+        let new_amount = amount.checked_sub(share).filter(|value| value > dust_limit).ok_or(
+            ConstructionError::SubtractFeeBelowDust {
+                index: *index,
+                amount: amount.checked_sub(share).unwrap_or(Sats::ZERO),
+            },
+        )?;
+        result.push((*index, new_amount));
+    }
+    Ok(result)
+}
+
+/// Whether `new_fee_rate` is strictly higher than the fee rate implied by
+/// `original_fee`/`original_size` (rounded down to whole sat/vB) - the replaceability requirement
+/// [`PsbtConstructor::bump_fee`] enforces before building a replacement transaction.
+fn fee_rate_increased(original_fee: Sats, original_size: u32, new_fee_rate: FeeRate) -> bool {
+    let original_rate = original_fee.to_sats() / (original_size as u64).max(1);
+    new_fee_rate.sat_per_vb() > original_rate
+}
+
+/// The additional value [`PsbtConstructor::bump_fee`] must select beyond `existing_value` - the
+/// total already held in the original transaction's inputs - to cover `output_value` plus
+/// `base_fee`, the part of the replacement's fee that does not depend on any newly selected
+/// inputs (the original inputs and all outputs). Zero if the original inputs already cover it.
+fn bump_fee_shortfall(output_value: Sats, base_fee: Sats, existing_value: Sats) -> Sats {
+    let needed = output_value.checked_add(base_fee).unwrap_or(output_value);
+    needed.checked_sub(existing_value).unwrap_or(Sats::ZERO)
+}
+
+/// Estimates the weight added by satisfying a single input of the given descriptor `class`,
+/// on top of [`TXIN_BASE_WEIGHT`]. Figures follow the standard signature and witness sizes used
+/// by most wallets (72-byte DER signatures, 33-byte compressed keys, 65-byte Schnorr
+/// signatures).
+pub(crate) fn input_satisfaction_weight(class: Class) -> u32 {
+    match class {
+        Class::Bare => 0,
+        Class::Pkh => (1 + 72 + 1 + 33) * 4,
+        Class::Sh => (1 + 72 + 1 + 33) * 4,
+        Class::Wpkh => 1 + 72 + 1 + 33,
+        Class::Wsh => 1 + 72 + 1 + 33,
+        Class::ShWpkh => (1 + 22) * 4 + 1 + 72 + 1 + 33,
+        Class::Tr => 1 + 65,
+    }
+}
+
+/// Estimates the serialized length, in bytes, of a `script_pubkey` produced for the given
+/// descriptor `class`; used to size a not-yet-derived change output.
+pub(crate) fn spk_len_estimate(class: Class) -> usize {
+    match class {
+        Class::Bare => 25,
+        Class::Pkh => 25,
+        Class::Sh => 23,
+        Class::ShWpkh => 23,
+        Class::Wpkh => 22,
+        Class::Wsh => 34,
+        Class::Tr => 34,
+    }
+}
+
+/// Estimates the weight, in weight units, of a transaction given the number of inputs, the
+/// descriptor `class` controlling them, and the serialized length of each output's
+/// `script_pubkey`.
+pub(crate) fn estimate_weight(input_count: usize, class: Class, output_spk_lens: impl Iterator<Item = usize>) -> u32 {
+    let mut weight = TX_BASE_WEIGHT;
+    weight += input_count as u32 * (TXIN_BASE_WEIGHT + input_satisfaction_weight(class));
+    for len in output_spk_lens {
+        weight += TXOUT_BASE_WEIGHT + len as u32 * 4;
+    }
+    weight
+}
+
+/// Estimates the virtual size (in vbytes) of a transaction; see [`estimate_weight`].
+pub(crate) fn estimate_vsize(input_count: usize, class: Class, output_spk_lens: impl Iterator<Item = usize>) -> u32 {
+    (estimate_weight(input_count, class, output_spk_lens) + 3) / 4
+}
+
 #[derive(Clone, Debug, Display, Error, From)]
 #[display(doc_comments)]
 pub enum ConstructionError {
@@ -66,6 +211,40 @@ pub enum ConstructionError {
 
     /// network for address {0} mismatches the one used by the wallet.
     NetworkMismatch(Address),
+
+    /// unable to select coins to fund the transaction.
+    #[from]
+    CoinSelection(CoinSelectionError),
+
+    /// none of the `subtract_fee_from` indices names a beneficiary output; at least one valid
+    /// beneficiary index must be given.
+    EmptySubtractFeeFrom,
+
+    /// `subtract_fee_from` cannot be combined with a `Payment::Max` beneficiary.
+    SubtractFeeWithMax,
+
+    /// subtracting the fee from output {index} would leave it with {amount} sats, below the
+    /// dust limit.
+    SubtractFeeBelowDust { index: usize, amount: Sats },
+
+    /// output {0} was named in `subtract_fee_from` but pays zero sats, so it cannot be assigned
+    /// a proportional share of the fee.
+    SubtractFeeFromZeroValue(usize),
+}
+
+#[derive(Clone, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum FeeBumpError {
+    #[display(inner)]
+    #[from]
+    Construction(ConstructionError),
+
+    /// the transaction being replaced does not signal BIP125 replaceability and cannot be
+    /// fee-bumped.
+    NotReplaceable,
+
+    /// the replacement fee rate does not strictly exceed the original transaction's fee rate.
+    FeeNotIncreased { original: Sats, replacement: Sats },
 }
 
 #[derive(Clone, Debug, Display, Error, From)]
@@ -74,6 +253,12 @@ pub enum BeneficiaryParseError {
     #[display("invalid format of the invoice")]
     InvalidFormat,
 
+    /// amount '{0}' is not a valid BTC decimal value.
+    InvalidAmount(String),
+
+    /// the invoice requires '{0}', which is not supported by this wallet.
+    UnsupportedRequirement(String),
+
     #[from]
     Int(ParseIntError),
 
@@ -120,63 +305,240 @@ impl FromStr for Payment {
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Display)]
-#[display("{amount}@{address}", alt = "bitcoin:{address}?amount={amount}")]
+/// Maximum payload size, in bytes, accepted by the default Bitcoin Core relay policy for a
+/// single `OP_RETURN` output.
+pub const OP_RETURN_RELAY_LIMIT: usize = 80;
+
+#[derive(Clone, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum OpReturnError {
+    /// OP_RETURN payload of {0} bytes exceeds the {OP_RETURN_RELAY_LIMIT}-byte relay limit.
+    TooLarge(usize),
+}
+
+fn op_return_script(data: &[u8]) -> ScriptPubkey {
+    let mut bytes = Vec::with_capacity(data.len() + 2);
+    bytes.push(0x6A); // OP_RETURN
+    match data.len() {
+        0 => {}
+        len @ 1..=75 => bytes.push(len as u8),
+        len => {
+            bytes.push(0x4C); // OP_PUSHDATA1
+            bytes.push(len as u8);
+        }
+    }
+    bytes.extend_from_slice(data);
+    ScriptPubkey::from(bytes)
+}
+
+/// Where a [`Beneficiary`] sends its funds: either a standard payment to an [`Address`], or a
+/// raw [`ScriptPubkey`] for non-standard scripts and data carriers (e.g. `OP_RETURN`).
+#[derive(Clone, Eq, PartialEq, Debug, Display)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Recipient {
+    #[display(inner)]
+    Address(Address),
+    #[display("script:{0:x}")]
+    Script(ScriptPubkey),
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Beneficiary {
-    pub address: Address,
+    pub recipient: Recipient,
     pub amount: Payment,
 }
 
+impl fmt::Display for Beneficiary {
+    /// The plain form is the legacy, crate-internal `amount@address` (or `amount@script:..`)
+    /// form. The alternate form (`{:#}`) renders a BIP21 `bitcoin:` URI, as understood by
+    /// [`Invoice`], for beneficiaries that pay a plain [`Address`] a fixed amount; it falls back
+    /// to the plain form for `OP_RETURN`/script recipients and `MAX` payments, which a `bitcoin:`
+    /// URI cannot express.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            if let (Recipient::Address(address), Some(amount)) =
+                (&self.recipient, self.amount.sats())
+            {
+                let mut invoice = Invoice::new(address.clone());
+                invoice.amount = Some(amount);
+                return write!(f, "{invoice}");
+            }
+        }
+        write!(f, "{}@{}", self.amount, self.recipient)
+    }
+}
+
 impl Beneficiary {
     #[inline]
     pub fn new(address: Address, amount: impl Into<Payment>) -> Self {
         Beneficiary {
-            address,
+            recipient: Recipient::Address(address),
             amount: amount.into(),
         }
     }
     #[inline]
     pub fn with_max(address: Address) -> Self {
         Beneficiary {
-            address,
+            recipient: Recipient::Address(address),
             amount: Payment::Max,
         }
     }
+    /// Creates a beneficiary paying a raw `script_pubkey` rather than an [`Address`], for
+    /// non-standard scripts or data carriers. An `amount` of zero is allowed.
+    #[inline]
+    pub fn with_script(script: ScriptPubkey, amount: impl Into<Payment>) -> Self {
+        Beneficiary {
+            recipient: Recipient::Script(script),
+            amount: amount.into(),
+        }
+    }
+    /// Creates a zero-value `OP_RETURN` output carrying `data`, rejecting payloads over the
+    /// [`OP_RETURN_RELAY_LIMIT`]-byte standardness limit.
+    pub fn op_return(data: &[u8]) -> Result<Self, OpReturnError> {
+        if data.len() > OP_RETURN_RELAY_LIMIT {
+            return Err(OpReturnError::TooLarge(data.len()));
+        }
+        Ok(Beneficiary::with_script(op_return_script(data), Sats::ZERO))
+    }
     #[inline]
     pub fn is_max(&self) -> bool { self.amount.is_max() }
     #[inline]
-    pub fn script_pubkey(&self) -> ScriptPubkey { self.address.script_pubkey() }
+    pub fn address(&self) -> Option<&Address> {
+        match &self.recipient {
+            Recipient::Address(address) => Some(address),
+            Recipient::Script(_) => None,
+        }
+    }
+    #[inline]
+    pub fn script_pubkey(&self) -> ScriptPubkey {
+        match &self.recipient {
+            Recipient::Address(address) => address.script_pubkey(),
+            Recipient::Script(script) => script.clone(),
+        }
+    }
 }
 
 impl FromStr for Beneficiary {
     type Err = BeneficiaryParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (amount, beneficiary) =
-            s.split_once('@').ok_or(BeneficiaryParseError::InvalidFormat)?;
-        Ok(Beneficiary::new(Address::from_str(beneficiary)?, Payment::from_str(amount)?))
+        // The legacy, crate-internal `amount@address` form.
+        if let Some((amount, beneficiary)) = s.split_once('@') {
+            return Ok(Beneficiary::new(Address::from_str(beneficiary)?, Payment::from_str(amount)?));
+        }
+        // A BIP21 `bitcoin:` URI; an amount is required since a `Beneficiary` must specify one.
+        let invoice = Invoice::from_str(s)?;
+        let amount = invoice.amount.ok_or(BeneficiaryParseError::InvalidFormat)?;
+        Ok(Beneficiary::new(invoice.address, amount))
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+/// A fee rate expressed in satoshis per virtual byte.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display)]
+#[display("{0} sat/vB")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FeeRate(u64);
+
+impl FeeRate {
+    pub const ZERO: Self = FeeRate(0);
+
+    #[inline]
+    pub fn from_sat_per_vb(rate: u64) -> Self { FeeRate(rate) }
+
+    #[inline]
+    pub fn sat_per_vb(self) -> u64 { self.0 }
+
+    /// Computes the absolute fee for a transaction of the given virtual size (in vbytes).
+    #[inline]
+    pub fn to_fee(self, vsize: u32) -> Sats { Sats::from_sats(self.0.saturating_mul(vsize as u64)) }
+}
+
+/// Fee specification for [`TxParams`]: either a fixed absolute amount, or a target fee rate
+/// from which the absolute fee is derived based on the estimated virtual size of the
+/// constructed transaction.
+#[derive(Copy, Clone, PartialEq, Debug, Display, From)]
+#[display(doc_comments)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FeeSpec {
+    /// fixed fee of {0} sats
+    #[from]
+    Absolute(Sats),
+    /// target fee rate of {0}
+    #[from]
+    Rate(FeeRate),
+}
+
+/// Sequence number used to opt in to BIP125 replace-by-fee signaling: the highest value that
+/// still signals replaceability, since any sequence number below `0xFFFFFFFE` does.
+const RBF_SEQUENCE: u32 = 0xFFFFFFFD;
+
+#[derive(Clone, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TxParams {
-    pub fee: Sats,
+    pub fee: FeeSpec,
     pub lock_time: Option<LockTime>,
     pub seq_no: SeqNo,
     pub change_shift: bool,
     pub change_keychain: Keychain,
+    /// Whether every input's sequence number should signal BIP125 replaceability, overriding
+    /// [`Self::seq_no`].
+    pub enable_rbf: bool,
+    /// Indices, into the `beneficiaries` passed to [`PsbtConstructor::construct_psbt`], of the
+    /// fixed-amount outputs that should bear the transaction fee themselves, deducted
+    /// proportionally to their declared amount, instead of the fee coming out of change. Used
+    /// for exact-drain sends where the recipient(s) should receive the wallet's full balance
+    /// minus fees, with no change output left over. Not compatible with `Payment::Max`
+    /// beneficiaries.
+    pub subtract_fee_from: Vec<usize>,
 }
 
 impl TxParams {
     pub fn with(fee: Sats) -> Self {
         TxParams {
-            fee,
+            fee: FeeSpec::Absolute(fee),
+            lock_time: None,
+            seq_no: SeqNo::from_consensus_u32(0),
+            change_shift: true,
+            change_keychain: Keychain::INNER,
+            enable_rbf: false,
+            subtract_fee_from: Vec::new(),
+        }
+    }
+
+    pub fn with_fee_rate(rate: FeeRate) -> Self {
+        TxParams {
+            fee: FeeSpec::Rate(rate),
             lock_time: None,
             seq_no: SeqNo::from_consensus_u32(0),
             change_shift: true,
             change_keychain: Keychain::INNER,
+            enable_rbf: false,
+            subtract_fee_from: Vec::new(),
+        }
+    }
+
+    /// Makes every input of the constructed transaction signal BIP125 replace-by-fee.
+    pub fn rbf(mut self) -> Self {
+        self.enable_rbf = true;
+        self
+    }
+
+    /// Marks the beneficiaries at `indices` (by position in the `beneficiaries` iterator passed
+    /// to [`PsbtConstructor::construct_psbt`]) as bearing the transaction fee themselves; see
+    /// [`Self::subtract_fee_from`].
+    pub fn subtract_fee_from(mut self, indices: impl IntoIterator<Item = usize>) -> Self {
+        self.subtract_fee_from = indices.into_iter().collect();
+        self
+    }
+
+    /// The sequence number that will actually be used for the transaction's inputs, taking
+    /// [`Self::enable_rbf`] into account.
+    pub fn effective_seq_no(&self) -> SeqNo {
+        if self.enable_rbf {
+            SeqNo::from_consensus_u32(RBF_SEQUENCE)
+        } else {
+            self.seq_no
         }
     }
 }
@@ -238,6 +600,7 @@ pub trait PsbtConstructor {
         }
 
         // 1. Add inputs
+        let seq_no = params.effective_seq_no();
         for coin in coins {
             let prev_tx = self.prev_tx(coin.txid).ok_or(ConstructionError::UnknownInput(coin))?;
             let (utxo, spk) = self.utxo(coin).ok_or_else(|| {
@@ -252,7 +615,7 @@ pub trait PsbtConstructor {
                 self.descriptor(),
                 utxo.terminal,
                 spk,
-                params.seq_no,
+                seq_no,
             );
         }
         if psbt.inputs().count() == 0 {
@@ -264,8 +627,12 @@ pub trait PsbtConstructor {
         let mut max = Vec::new();
         let mut output_value = Sats::ZERO;
         for beneficiary in beneficiaries {
-            if beneficiary.address.network != self.network().into() {
-                return Err(ConstructionError::NetworkMismatch(beneficiary.address));
+            // The network-mismatch check only makes sense for address-based recipients; a raw
+            // script is not tied to a network.
+            if let Some(address) = beneficiary.address() {
+                if address.network != self.network().into() {
+                    return Err(ConstructionError::NetworkMismatch(address.clone()));
+                }
             }
             let amount = beneficiary.amount.unwrap_or(Sats::ZERO);
             output_value
@@ -276,17 +643,107 @@ pub trait PsbtConstructor {
                 max.push(out.index());
             }
         }
+        let class = self.descriptor().class();
+        let input_count = psbt.inputs().count();
+        let dust_limit = class.dust_limit();
+
+        // The fee is estimated in up to two passes, since adding a change output changes the
+        // transaction size: first assuming no change output, then - if change survives the dust
+        // limit - once more with the change output's weight included.
+        let fee_for = |with_change: bool| -> Sats {
+            match params.fee {
+                FeeSpec::Absolute(fee) => fee,
+                FeeSpec::Rate(rate) => {
+                    let lens = psbt
+                        .outputs()
+                        .map(|out| out.script_pubkey.len())
+                        .chain(with_change.then_some(spk_len_estimate(class)));
+                    rate.to_fee(estimate_vsize(input_count, class, lens))
+                }
+            }
+        };
+
+        let fee_no_change = fee_for(false);
+        let fee_with_change = fee_for(true);
+
+        // If requested, the fee is carved out of specific recipient outputs instead of change,
+        // for exact-drain sends that leave no change output behind.
+        if !params.subtract_fee_from.is_empty() {
+            if !max.is_empty() {
+                return Err(ConstructionError::SubtractFeeWithMax);
+            }
+            let slack = input_value.checked_sub(output_value).ok_or(
+                ConstructionError::OutputExceedsInputs {
+                    input_value,
+                    output_value,
+                },
+            )?;
+
+            let targets: Vec<(usize, Sats, Sats)> = psbt
+                .outputs()
+                .filter(|out| params.subtract_fee_from.contains(&out.index()))
+                .map(|out| (out.index(), out.amount, dust_limit_for(&out.script_pubkey, class)))
+                .collect();
+            if targets.is_empty() {
+                return Err(ConstructionError::EmptySubtractFeeFrom);
+            }
+            let new_amounts = compute_fee_shares(fee_no_change, &targets)?;
+            for (index, new_amount) in new_amounts {
+                for out in psbt.outputs_mut() {
+                    if out.index() == index {
+                        out.amount = new_amount;
+                    }
+                }
+            }
+
+            // Slack beyond the declared beneficiary amounts is only "drain dust" if it can't
+            // justify its own change output; otherwise a non-draining caller setting
+            // `subtract_fee_from` would silently pay their whole remaining balance as fee.
+            let extra_fee_for_change =
+                fee_with_change.checked_sub(fee_no_change).unwrap_or(Sats::ZERO);
+            let (change, fee) = if slack > dust_limit
+                && slack.checked_sub(extra_fee_for_change).is_some_and(|v| v > dust_limit)
+            {
+                let change_value = slack - extra_fee_for_change;
+                let change_index =
+                    self.next_derivation_index(params.change_keychain, params.change_shift);
+                let change_terminal = Terminal::new(params.change_keychain, change_index);
+                let change_vout = psbt
+                    .append_change_expect(self.descriptor(), change_terminal, change_value)
+                    .index();
+                let change = Some(ChangeInfo {
+                    vout: Vout::from_u32(change_vout as u32),
+                    terminal: change_terminal,
+                });
+                (change, fee_no_change + extra_fee_for_change)
+            } else {
+                (None, fee_no_change.checked_add(slack).unwrap_or(fee_no_change))
+            };
+
+            let weight =
+                estimate_weight(input_count, class, psbt.outputs().map(|out| out.script_pubkey.len()));
+            let meta = PsbtMeta {
+                network: self.network().into(),
+                fee,
+                weight,
+                size: (weight + 3) / 4,
+                change,
+            };
+            self.after_construct_psbt(&psbt, &meta);
+            return Ok((psbt, meta));
+        }
+
         let mut remaining_value = input_value
             .checked_sub(output_value)
             .ok_or(ConstructionError::OutputExceedsInputs {
                 input_value,
                 output_value,
             })?
-            .checked_sub(params.fee)
+            .checked_sub(fee_no_change)
             .ok_or(ConstructionError::NoFundsForFee {
                 input_value,
                 output_value,
-                fee: params.fee,
+                fee: fee_no_change,
             })?;
         if !max.is_empty() {
             let portion = remaining_value / max.len();
@@ -298,27 +755,36 @@ pub trait PsbtConstructor {
             remaining_value = Sats::ZERO;
         }
 
-        // 3. Add change - only if exceeded the dust limit
-        let change = if remaining_value > self.descriptor().class().dust_limit() {
+        // 3. Add change - only if it exceeds the dust limit once its own cost is accounted for
+        let extra_fee_for_change = fee_with_change.checked_sub(fee_no_change).unwrap_or(Sats::ZERO);
+        let (change, fee) = if max.is_empty()
+            && remaining_value > dust_limit
+            && remaining_value.checked_sub(extra_fee_for_change).is_some_and(|v| v > dust_limit)
+        {
+            let change_value = remaining_value - extra_fee_for_change;
             let change_index =
                 self.next_derivation_index(params.change_keychain, params.change_shift);
             let change_terminal = Terminal::new(params.change_keychain, change_index);
             let change_vout = psbt
-                .append_change_expect(self.descriptor(), change_terminal, remaining_value)
+                .append_change_expect(self.descriptor(), change_terminal, change_value)
                 .index();
-            Some(ChangeInfo {
+            let change = Some(ChangeInfo {
                 vout: Vout::from_u32(change_vout as u32),
                 terminal: change_terminal,
-            })
+            });
+            (change, fee_no_change + extra_fee_for_change)
         } else {
-            None
+            // Leftover value below the dust limit is folded into the fee rather than creating an
+            // uneconomical change output.
+            (None, fee_no_change + remaining_value)
         };
 
+        let weight = estimate_weight(input_count, class, psbt.outputs().map(|out| out.script_pubkey.len()));
         let meta = PsbtMeta {
             network: self.network().into(),
-            fee: params.fee,
-            weight: 0, // TODO: Implement weight/size computation
-            size: 0,
+            fee,
+            weight,
+            size: (weight + 3) / 4,
             change,
         };
         self.after_construct_psbt(&psbt, &meta);
@@ -331,4 +797,232 @@ pub trait PsbtConstructor {
     fn after_construct_psbt(&mut self, _psbt: &Psbt, _meta: &PsbtMeta) {
         // By default, we do not use the hook
     }
+
+    /// Returns the set of spendable coins known to the wallet, used as candidates for automatic
+    /// coin selection in [`Self::construct_psbt_auto`]. The default implementation returns no
+    /// candidates; wallets wishing to use automatic selection must override this.
+    fn available_utxos(&self) -> Vec<Utxo> { Vec::new() }
+
+    /// Like [`Self::construct_psbt`], but instead of being given a pre-selected set of coins,
+    /// chooses them itself from [`Self::available_utxos`] using `selector`.
+    fn construct_psbt_auto(
+        &mut self,
+        selector: &impl CoinSelector,
+        beneficiaries: impl IntoIterator<Item = Beneficiary>,
+        params: TxParams,
+    ) -> Result<(Psbt, PsbtMeta), ConstructionError> {
+        let beneficiaries = beneficiaries.into_iter().collect::<Vec<_>>();
+
+        let mut output_value = Sats::ZERO;
+        for beneficiary in &beneficiaries {
+            output_value
+                .checked_add_assign(beneficiary.amount.unwrap_or(Sats::ZERO))
+                .ok_or(ConstructionError::Overflow(output_value))?;
+        }
+
+        let class = self.descriptor().class();
+        let input_weight = TXIN_BASE_WEIGHT + input_satisfaction_weight(class);
+        let change_output_weight = TXOUT_BASE_WEIGHT + spk_len_estimate(class) as u32 * 4;
+        let base_weight =
+            estimate_weight(0, class, beneficiaries.iter().map(|b| b.script_pubkey().len()));
+
+        // The per-input cost of spending each candidate is already charged against its effective
+        // value inside `selector`; `target` only needs to cover the declared outputs plus the
+        // fixed, non-input part of the transaction - which for an absolute fee is just the fee
+        // itself, reserved in full regardless of how many inputs end up selected.
+        let (input_fee_rate, base_fee, cost_of_change) = match params.fee {
+            FeeSpec::Absolute(fee) => (FeeRate::ZERO, fee, Sats::ZERO),
+            FeeSpec::Rate(rate) => (
+                rate,
+                rate.to_fee((base_weight + 3) / 4),
+                rate.to_fee((change_output_weight + input_weight + 3) / 4),
+            ),
+        };
+        let target = output_value
+            .checked_add(base_fee)
+            .ok_or(ConstructionError::Overflow(output_value))?;
+
+        let candidates = self.available_utxos();
+        let selection =
+            selector.select(&candidates, target, input_fee_rate, input_weight, cost_of_change)?;
+        let coins = selection.selected.into_iter().map(|utxo| utxo.outpoint);
+
+        self.construct_psbt(coins, beneficiaries, params)
+    }
+
+    /// Builds a BIP125-compliant replacement for `original`, an unsigned transaction previously
+    /// produced by this wallet, at a strictly higher `new_fee_rate`.
+    ///
+    /// The replacement reuses `original`'s inputs, pulling in further coins via `selector` only
+    /// if the higher fee can no longer be covered by them alone; it pays the same
+    /// `beneficiaries` and lets [`Self::construct_psbt`] shrink (or drop) the change output to
+    /// absorb the extra fee. Errors if `original` does not signal replaceability, or if
+    /// `new_fee_rate` does not exceed the fee rate implied by `original_meta`.
+    fn bump_fee(
+        &mut self,
+        original: &UnsignedTx,
+        original_meta: &PsbtMeta,
+        beneficiaries: impl IntoIterator<Item = Beneficiary>,
+        new_fee_rate: FeeRate,
+        selector: &impl CoinSelector,
+    ) -> Result<(Psbt, PsbtMeta), FeeBumpError> {
+        let signals_rbf =
+            original.inputs().any(|inp| inp.sequence.to_consensus_u32() <= RBF_SEQUENCE);
+        if !signals_rbf {
+            return Err(FeeBumpError::NotReplaceable);
+        }
+
+        if !fee_rate_increased(original_meta.fee, original_meta.size, new_fee_rate) {
+            return Err(FeeBumpError::FeeNotIncreased {
+                original: original_meta.fee,
+                replacement: new_fee_rate.to_fee(original_meta.size),
+            });
+        }
+
+        let beneficiaries = beneficiaries.into_iter().collect::<Vec<_>>();
+        let mut coins: Vec<Outpoint> =
+            original.inputs().map(|inp| inp.previous_outpoint).collect();
+        let params = TxParams::with_fee_rate(new_fee_rate).rbf();
+
+        let result = match self.construct_psbt(coins.iter().copied(), beneficiaries.clone(), params.clone()) {
+            Err(ConstructionError::NoFundsForFee { .. } | ConstructionError::OutputExceedsInputs { .. }) => {
+                // The original inputs no longer cover the higher fee; pull in just enough extra
+                // coins to make up the shortfall, rather than re-funding the whole payment.
+                let class = self.descriptor().class();
+                let input_weight = TXIN_BASE_WEIGHT + input_satisfaction_weight(class);
+                let change_output_weight = TXOUT_BASE_WEIGHT + spk_len_estimate(class) as u32 * 4;
+                let cost_of_change =
+                    new_fee_rate.to_fee((change_output_weight + input_weight + 3) / 4);
+
+                let output_value = beneficiaries
+                    .iter()
+                    .filter_map(|b| b.amount.sats())
+                    .fold(Sats::ZERO, |acc, v| acc.checked_add(v).unwrap_or(acc));
+                // `coins` already holds the original transaction's inputs, so the fee baseline
+                // must charge for them too - unlike `construct_psbt_auto`, where no inputs are
+                // committed yet and the baseline is legitimately input-count zero.
+                let base_weight = estimate_weight(
+                    coins.len(),
+                    class,
+                    beneficiaries.iter().map(|b| b.script_pubkey().len()),
+                );
+                let base_fee = new_fee_rate.to_fee((base_weight + 3) / 4);
+                let existing_value = coins
+                    .iter()
+                    .filter_map(|outpoint| self.utxo(*outpoint))
+                    .map(|(utxo, _)| utxo.value)
+                    .fold(Sats::ZERO, |acc, v| acc.checked_add(v).unwrap_or(acc));
+                let target = bump_fee_shortfall(output_value, base_fee, existing_value);
+
+                let candidates: Vec<Utxo> = self
+                    .available_utxos()
+                    .into_iter()
+                    .filter(|utxo| !coins.contains(&utxo.outpoint))
+                    .collect();
+                let selection = selector
+                    .select(&candidates, target, new_fee_rate, input_weight, cost_of_change)
+                    .map_err(ConstructionError::from)?;
+                coins.extend(selection.selected.into_iter().map(|utxo| utxo.outpoint));
+                self.construct_psbt(coins, beneficiaries, params)
+            }
+            other => other,
+        };
+        Ok(result?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_fee_shares_splits_proportionally() {
+        let targets =
+            vec![(0, Sats::from_sats(1_000), Sats::ZERO), (1, Sats::from_sats(3_000), Sats::ZERO)];
+        let shares = compute_fee_shares(Sats::from_sats(400), &targets).unwrap();
+        // 1_000 of a 4_000 total gets a quarter of the fee, 3_000 gets the rest - including any
+        // rounding remainder, since the last target always absorbs it.
+        assert_eq!(shares, vec![(0, Sats::from_sats(900)), (1, Sats::from_sats(2_700))]);
+    }
+
+    #[test]
+    fn compute_fee_shares_does_not_overflow_on_large_amounts() {
+        // Two equal, near-u64::MAX targets: a naive `fee * amount` u64 multiply overflows long
+        // before the divide, even though the true proportional share (half the fee each) fits
+        // trivially.
+        let huge = Sats::from_sats(u64::MAX / 2);
+        let targets = vec![(0, huge, Sats::ZERO), (1, huge, Sats::ZERO)];
+        let shares = compute_fee_shares(Sats::from_sats(10_000), &targets).unwrap();
+        assert_eq!(
+            shares,
+            vec![
+                (0, huge.checked_sub(Sats::from_sats(5_000)).unwrap()),
+                (1, huge.checked_sub(Sats::from_sats(5_000)).unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn compute_fee_shares_rejects_zero_amount_target() {
+        let targets = vec![(0, Sats::ZERO, Sats::ZERO), (1, Sats::from_sats(1_000), Sats::ZERO)];
+        let err = compute_fee_shares(Sats::from_sats(100), &targets).unwrap_err();
+        assert!(matches!(err, ConstructionError::SubtractFeeFromZeroValue(0)));
+    }
+
+    #[test]
+    fn compute_fee_shares_rejects_below_dust_result() {
+        let targets = vec![(0, Sats::from_sats(500), Sats::from_sats(400))];
+        let err = compute_fee_shares(Sats::from_sats(200), &targets).unwrap_err();
+        assert!(matches!(err, ConstructionError::SubtractFeeBelowDust { index: 0, .. }));
+    }
+
+    #[test]
+    fn dust_limit_for_matches_recipient_script_type_not_wallet_class() {
+        // A P2WPKH wallet (dust ~294 sats) paying a P2PKH address must use the P2PKH dust limit
+        // (~546 sats), not its own.
+        let p2pkh = ScriptPubkey::from({
+            let mut bytes = vec![0x76, 0xA9, 0x14];
+            bytes.extend_from_slice(&[0u8; 20]);
+            bytes.push(0x88);
+            bytes.push(0xAC);
+            bytes
+        });
+        assert_eq!(dust_limit_for(&p2pkh, Class::Wpkh), Sats::from_sats(546));
+
+        let p2wpkh = ScriptPubkey::from({
+            let mut bytes = vec![0x00, 0x14];
+            bytes.extend_from_slice(&[0u8; 20]);
+            bytes
+        });
+        assert_eq!(dust_limit_for(&p2wpkh, Class::Wpkh), Sats::from_sats(294));
+    }
+
+    #[test]
+    fn fee_rate_increased_requires_strictly_higher_rate() {
+        // 10_000 sats over a 250 vbyte transaction is exactly 40 sat/vB.
+        assert!(!fee_rate_increased(Sats::from_sats(10_000), 250, FeeRate::from_sat_per_vb(40)));
+        assert!(fee_rate_increased(Sats::from_sats(10_000), 250, FeeRate::from_sat_per_vb(41)));
+    }
+
+    #[test]
+    fn bump_fee_shortfall_nets_out_the_original_inputs() {
+        // From the worked example: one 52_000-sat input, a 50_000-sat payment, bumping to a fee
+        // that requires ~3_500 sats more than the payment alone.
+        let shortfall = bump_fee_shortfall(
+            Sats::from_sats(50_000),
+            Sats::from_sats(3_500),
+            Sats::from_sats(52_000),
+        );
+        assert_eq!(shortfall, Sats::from_sats(1_500));
+    }
+
+    #[test]
+    fn bump_fee_shortfall_is_zero_once_original_inputs_cover_it() {
+        let shortfall = bump_fee_shortfall(
+            Sats::from_sats(50_000),
+            Sats::from_sats(3_500),
+            Sats::from_sats(60_000),
+        );
+        assert_eq!(shortfall, Sats::ZERO);
+    }
 }
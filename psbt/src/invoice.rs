@@ -0,0 +1,194 @@
+// Modern, minimalistic & standard-compliant Bitcoin library.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Designed in 2019-2025 by Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+// Written in 2024-2025 by Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association, Switzerland.
+// Copyright (C) 2024-2025 LNP/BP Labs, Institute for Distributed and Cognitive Systems (InDCS).
+// Copyright (C) 2019-2025 Dr Maxim Orlovsky.
+// All rights under the above copyrights are reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use derive::{Address, Sats};
+
+use crate::BeneficiaryParseError;
+
+/// The URI scheme mandated by BIP21.
+const URI_SCHEME: &str = "bitcoin:";
+
+/// A BIP21 `bitcoin:` payment request, as scanned from a QR code or pasted from a wallet.
+///
+/// Round-trips through [`Display`]/[`FromStr`] to the canonical `bitcoin:<address>?...` form.
+/// Unknown, non-`req-`-prefixed parameters are preserved in [`Self::extra`] so that re-emitting
+/// an invoice does not silently drop information the wallet did not understand.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Invoice {
+    pub address: Address,
+    pub amount: Option<Sats>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    /// The `pj=` payjoin endpoint, if the invoice advertises one.
+    pub payjoin: Option<String>,
+    /// Unrecognized, non-required parameters, preserved for round-tripping.
+    pub extra: BTreeMap<String, String>,
+}
+
+impl Invoice {
+    pub fn new(address: Address) -> Self {
+        Invoice {
+            address,
+            amount: None,
+            label: None,
+            message: None,
+            payjoin: None,
+            extra: BTreeMap::new(),
+        }
+    }
+}
+
+impl FromStr for Invoice {
+    type Err = BeneficiaryParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let body = s.strip_prefix(URI_SCHEME).ok_or(BeneficiaryParseError::InvalidFormat)?;
+        let (addr, query) = match body.split_once('?') {
+            Some((addr, query)) => (addr, Some(query)),
+            None => (body, None),
+        };
+        let mut invoice = Invoice::new(Address::from_str(addr)?);
+
+        for pair in query.into_iter().flat_map(|query| query.split('&')).filter(|s| !s.is_empty()) {
+            let (key, raw_value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = percent_decode(raw_value);
+            match key {
+                "amount" => invoice.amount = Some(parse_btc_amount(&value)?),
+                "label" => invoice.label = Some(value),
+                "message" => invoice.message = Some(value),
+                "pj" => invoice.payjoin = Some(value),
+                _ if key.starts_with("req-") => {
+                    return Err(BeneficiaryParseError::UnsupportedRequirement(key.to_string()));
+                }
+                _ => {
+                    invoice.extra.insert(key.to_string(), value);
+                }
+            }
+        }
+        Ok(invoice)
+    }
+}
+
+impl fmt::Display for Invoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{URI_SCHEME}{}", self.address)?;
+        let mut params = Vec::new();
+        if let Some(amount) = self.amount {
+            params.push(format!("amount={}", format_btc_amount(amount)));
+        }
+        if let Some(label) = &self.label {
+            params.push(format!("label={}", percent_encode(label)));
+        }
+        if let Some(message) = &self.message {
+            params.push(format!("message={}", percent_encode(message)));
+        }
+        if let Some(pj) = &self.payjoin {
+            params.push(format!("pj={}", percent_encode(pj)));
+        }
+        for (key, value) in &self.extra {
+            params.push(format!("{key}={}", percent_encode(value)));
+        }
+        if !params.is_empty() {
+            write!(f, "?{}", params.join("&"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a BIP21 `amount` parameter: a decimal BTC value with up to 8 fractional digits.
+fn parse_btc_amount(s: &str) -> Result<Sats, BeneficiaryParseError> {
+    let (whole, frac) = s.split_once('.').unwrap_or((s, ""));
+    let valid = frac.len() <= 8
+        && !whole.is_empty()
+        && whole.bytes().all(|b| b.is_ascii_digit())
+        && frac.bytes().all(|b| b.is_ascii_digit());
+    if !valid {
+        return Err(BeneficiaryParseError::InvalidAmount(s.to_string()));
+    }
+    let whole: u64 =
+        whole.parse().map_err(|_| BeneficiaryParseError::InvalidAmount(s.to_string()))?;
+    let mut frac = frac.to_string();
+    frac.push_str(&"0".repeat(8 - frac.len()));
+    let frac: u64 = frac.parse().map_err(|_| BeneficiaryParseError::InvalidAmount(s.to_string()))?;
+    let sats = whole
+        .checked_mul(100_000_000)
+        .and_then(|v| v.checked_add(frac))
+        .ok_or_else(|| BeneficiaryParseError::InvalidAmount(s.to_string()))?;
+    Ok(Sats::from_sats(sats))
+}
+
+/// Formats a [`Sats`] amount as a BIP21 decimal BTC `amount` value.
+fn format_btc_amount(amount: Sats) -> String {
+    let sats = amount.to_sats();
+    format!("{}.{:08}", sats / 100_000_000, sats % 100_000_000)
+}
+
+/// Decodes a `application/x-www-form-urlencoded`-style percent-encoded string.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 3;
+                    }
+                    None => {
+                        out.push(bytes[i]);
+                        i += 1;
+                    }
+                }
+            }
+            b'+' => {
+                out.push(b' ');
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Percent-encodes everything except unreserved characters (`A-Za-z0-9-_.~`).
+fn percent_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(b as char),
+            _ => out.push_str(&format!("%{b:02X}")),
+        }
+    }
+    out
+}